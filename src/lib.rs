@@ -1,19 +1,92 @@
 use anyhow::Context as _;
 use async_minecraft_ping::ConnectionConfig;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
 use serenity::async_trait;
 use serenity::builder::CreateEmbed;
 use serenity::builder::CreateEmbedFooter;
 use serenity::model::gateway::Ready;
+use serenity::model::prelude::component::ButtonStyle;
 use serenity::model::prelude::interaction::Interaction;
 use serenity::model::prelude::interaction::InteractionResponseType;
+use serenity::model::prelude::ChannelId;
 use serenity::model::prelude::GuildId;
+use serenity::model::prelude::MessageId;
 use serenity::prelude::*;
 use shuttle_secrets::SecretStore;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 use tracing::{error, info};
 
+/// How often the background monitor polls each subscribed server, unless the
+/// guild configured its own interval.
+const MONITOR_INTERVAL_SECS: u64 = 60;
+
+/// A named Minecraft server the bot can report on.
+#[derive(Clone, Serialize, Deserialize)]
+struct McServer {
+    name: String,
+    addr: String,
+    port: u16,
+}
+
+/// Durable per-guild configuration persisted in the embedded store so state
+/// survives restarts and redeploys.
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct GuildSettings {
+    servers: Vec<McServer>,
+    #[serde(default)]
+    subscriptions: HashSet<ChannelId>,
+    #[serde(default)]
+    poll_interval_secs: Option<u64>,
+    #[serde(default)]
+    pinned_message: Option<(ChannelId, MessageId)>,
+}
+
+/// Thin wrapper over a `sled` database holding one `GuildSettings` per guild.
+#[derive(Clone)]
+struct Store {
+    db: sled::Db,
+}
+
+impl Store {
+    fn open(path: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            db: sled::open(path)?,
+        })
+    }
+
+    fn load(&self, guild: GuildId) -> anyhow::Result<GuildSettings> {
+        match self.db.get(guild.0.to_be_bytes())? {
+            Some(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            None => Ok(GuildSettings::default()),
+        }
+    }
+
+    fn save(&self, guild: GuildId, settings: &GuildSettings) -> anyhow::Result<()> {
+        self.db
+            .insert(guild.0.to_be_bytes(), serde_json::to_vec(settings)?)?;
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+/// Per-server monitoring state carried between polls by the background task.
+#[derive(Clone, Default)]
+struct ServerState {
+    online: Option<bool>,
+    failures: u32,
+    last_count: u32,
+}
+
 struct Bot {
     discord_guild_id: GuildId,
-    mc_server: (String, u16),
+    /// Persistent backing store; mutations are written through to it.
+    store: Store,
+    /// In-memory view of the guild's settings, loaded from `store` at startup.
+    settings: Arc<Mutex<GuildSettings>>,
 }
 
 #[async_trait]
@@ -23,47 +96,372 @@ impl EventHandler for Bot {
 
         let commands =
             GuildId::set_application_commands(&self.discord_guild_id, &ctx.http, |commands| {
-                commands.create_application_command(|command| {
-                    command.name("status").description("Get Server Status")
-                })
+                commands
+                    .create_application_command(|command| {
+                        command.name("status").description("Get Server Status")
+                    })
+                    .create_application_command(|command| {
+                        command
+                            .name("subscribe")
+                            .description("Watch this channel for server online/offline transitions")
+                    })
+                    .create_application_command(|command| {
+                        command
+                            .name("pin-status")
+                            .description("Create a status message that refreshes itself")
+                    })
             })
             .await
             .unwrap();
 
         info!("Registered commands: {:#?}", commands);
+
+        self.spawn_monitor(ctx);
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::ApplicationCommand(command) = interaction {
-            let response_content = match command.data.name.as_str() {
-                "status" => {
-                    match get_server_status(&__self.mc_server.0, __self.mc_server.1).await {
-                        Ok(message) => message,
-                        Err(err) => {
-                            error!(?err, "Error while getting data from the MC server");
-                            CreateEmbed::default()
-                                .description(err.to_string())
-                                .to_owned()
-                        }
+        match interaction {
+            Interaction::ApplicationCommand(command) => {
+                let servers = self.servers().await;
+
+                // `/status` with more than one configured server first offers a
+                // picker; everything else responds with a single embed.
+                if command.data.name == "status" && servers.len() > 1 {
+                    let create_interaction_response =
+                        command.create_interaction_response(&ctx.http, |response| {
+                            response
+                                .kind(InteractionResponseType::ChannelMessageWithSource)
+                                .interaction_response_data(|message| {
+                                    message.content("Pick a server:").components(|components| {
+                                        add_server_menu(components, &servers)
+                                    })
+                                })
+                        });
+
+                    if let Err(why) = create_interaction_response.await {
+                        eprintln!("Cannot respond to slash command: {}", why);
+                    }
+                    return;
+                }
+
+                let response_content: ServerStatus = match command.data.name.as_str() {
+                    "status" => self.status_embed(&servers[0]).await,
+                    "subscribe" => self.subscribe(command.channel_id).await.into(),
+                    "pin-status" => self.pin_status(&ctx, command.channel_id).await.into(),
+                    command => unreachable!("Unknown command: {}", command),
+                };
+
+                let with_button = command.data.name == "status";
+
+                let create_interaction_response =
+                    command.create_interaction_response(&ctx.http, |response| {
+                        response
+                            .kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|message| {
+                                message.add_embed(response_content.embed);
+                                if let Some(favicon) = response_content.favicon {
+                                    message.add_file((favicon.as_slice(), FAVICON_FILE));
+                                }
+                                if with_button {
+                                    message.components(|components| {
+                                        add_refresh_button(components, &servers[0].name)
+                                    });
+                                }
+                                message
+                            })
+                    });
+
+                if let Err(why) = create_interaction_response.await {
+                    eprintln!("Cannot respond to slash command: {}", why);
+                }
+            }
+            Interaction::MessageComponent(component) => {
+                let servers = self.servers().await;
+                let custom_id = component.data.custom_id.as_str();
+                // Resolve which server the interaction targets: the name encoded
+                // in the Refresh button, or the one chosen from the select menu.
+                let selected = if let Some(name) = custom_id.strip_prefix("status_refresh:") {
+                    servers.iter().find(|s| s.name == name)
+                } else if custom_id == "status_select" {
+                    component
+                        .data
+                        .values
+                        .first()
+                        .and_then(|name| servers.iter().find(|s| &s.name == name))
+                } else {
+                    None
+                };
+
+                if let Some(server) = selected {
+                    let response_content = self.status_embed(server).await;
+                    let server_name = server.name.clone();
+                    let create_interaction_response =
+                        component.create_interaction_response(&ctx.http, |response| {
+                            response
+                                .kind(InteractionResponseType::UpdateMessage)
+                                .interaction_response_data(|message| {
+                                    message.add_embed(response_content.embed);
+                                    if let Some(favicon) = response_content.favicon {
+                                        message.add_file((favicon.as_slice(), FAVICON_FILE));
+                                    }
+                                    message.components(|components| {
+                                        add_refresh_button(components, &server_name)
+                                    })
+                                })
+                        });
+
+                    if let Err(why) = create_interaction_response.await {
+                        eprintln!("Cannot respond to component interaction: {}", why);
                     }
                 }
-                command => unreachable!("Unknown command: {}", command),
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Bot {
+    /// Poll `server` and build the status, falling back to an error embed if it
+    /// cannot be reached.
+    async fn status_embed(&self, server: &McServer) -> ServerStatus {
+        match get_server_status(&server.addr, server.port).await {
+            Ok(status) => status,
+            Err(err) => {
+                error!(?err, "Error while getting data from the MC server");
+                ServerStatus {
+                    embed: CreateEmbed::default().description(err.to_string()).to_owned(),
+                    favicon: None,
+                }
+            }
+        }
+    }
+
+    /// Snapshot of the guild's configured servers.
+    async fn servers(&self) -> Vec<McServer> {
+        self.settings.lock().await.servers.clone()
+    }
+
+    /// Write the current in-memory settings through to the backing store.
+    async fn persist(&self) {
+        let settings = self.settings.lock().await;
+        if let Err(why) = self.store.save(self.discord_guild_id, &settings) {
+            error!(?why, "Failed to persist guild settings");
+        }
+    }
+
+    /// Register `channel` for online/offline monitoring and confirm to the user.
+    async fn subscribe(&self, channel: ChannelId) -> CreateEmbed {
+        let added = self.settings.lock().await.subscriptions.insert(channel);
+        if added {
+            self.persist().await;
+        }
+        let text = if added {
+            "This channel is now subscribed to server status updates."
+        } else {
+            "This channel is already subscribed to server status updates."
+        };
+        CreateEmbed::default().description(text).to_owned()
+    }
+
+    /// Create the self-refreshing dashboard message in `channel`, recording its
+    /// id so the background task can edit it in place on every tick.
+    async fn pin_status(&self, ctx: &Context, channel: ChannelId) -> CreateEmbed {
+        let server = self.servers().await.remove(0);
+        let status = self.status_embed(&server).await;
+        match channel
+            .send_message(&ctx.http, |message| {
+                message.set_embed(status.embed.clone());
+                if let Some(favicon) = &status.favicon {
+                    message.add_file((favicon.as_slice(), FAVICON_FILE));
+                }
+                message
+            })
+            .await
+        {
+            Ok(message) => {
+                self.settings.lock().await.pinned_message = Some((channel, message.id));
+                self.persist().await;
+                CreateEmbed::default()
+                    .description("Posted a self-refreshing status message to this channel.")
+                    .to_owned()
+            }
+            Err(why) => {
+                error!(?why, "Failed to create pinned status message");
+                CreateEmbed::default()
+                    .description("Could not create the status message.")
+                    .to_owned()
+            }
+        }
+    }
+
+    /// Spawn the background task that polls the server and posts an embed to
+    /// every subscribed channel whenever the reachability state flips. Flapping
+    /// is debounced by requiring two consecutive failures before declaring the
+    /// server offline.
+    fn spawn_monitor(&self, ctx: Context) {
+        let settings = self.settings.clone();
+
+        tokio::spawn(async move {
+            // Snapshot the server list and poll interval once; nothing changes
+            // them while the bot is running.
+            let (servers, interval_secs) = {
+                let settings = settings.lock().await;
+                (
+                    settings.servers.clone(),
+                    settings.poll_interval_secs.unwrap_or(MONITOR_INTERVAL_SECS),
+                )
             };
+            let mut interval =
+                tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+            // Per-server reachability, consecutive-failure count and last seen
+            // player count, tracked independently so each server flips on its own.
+            let mut state: Vec<ServerState> = vec![ServerState::default(); servers.len()];
+
+            loop {
+                interval.tick().await;
 
-            let create_interaction_response =
-                command.create_interaction_response(&ctx.http, |response| {
-                    response
-                        .kind(InteractionResponseType::ChannelMessageWithSource)
-                        .interaction_response_data(|message| message.add_embed(response_content))
-                });
+                let (channels, pinned): (Vec<ChannelId>, Option<(ChannelId, MessageId)>) = {
+                    let settings = settings.lock().await;
+                    (
+                        settings.subscriptions.iter().copied().collect(),
+                        settings.pinned_message,
+                    )
+                };
+
+                for (server, state) in servers.iter().zip(state.iter_mut()) {
+                    if channels.is_empty() {
+                        break;
+                    }
+                    // Prefix with the server name only when more than one is
+                    // configured, so the single-server case reads naturally.
+                    let label = if servers.len() > 1 {
+                        format!("**{}** ", server.name)
+                    } else {
+                        String::new()
+                    };
+
+                    match poll_server(&server.addr, server.port).await {
+                        Ok((count, max)) => {
+                            state.failures = 0;
+                            // Only announce a genuine flip. The first observation
+                            // (`online == None`) seeds state silently so a redeploy
+                            // does not blast a bogus "back online" to every channel.
+                            match state.online {
+                                Some(false) => {
+                                    announce(
+                                        &ctx,
+                                        &channels,
+                                        &format!("{label}is back online ({count}/{max} players)"),
+                                    )
+                                    .await;
+                                }
+                                Some(true) if state.last_count == 0 && count > 0 => {
+                                    announce(
+                                        &ctx,
+                                        &channels,
+                                        &format!("{label}has players ({count}/{max} players)"),
+                                    )
+                                    .await;
+                                }
+                                _ => {}
+                            }
+                            state.online = Some(true);
+                            state.last_count = count;
+                        }
+                        Err(err) => {
+                            state.failures += 1;
+                            if state.failures >= 2 {
+                                match state.online {
+                                    Some(true) => {
+                                        error!(?err, server = %server.name, "Server became unreachable");
+                                        announce(&ctx, &channels, &format!("{label}went offline"))
+                                            .await;
+                                        state.online = Some(false);
+                                    }
+                                    // Never seen online yet: seed offline silently.
+                                    None => state.online = Some(false),
+                                    Some(false) => {}
+                                }
+                            }
+                        }
+                    }
+                }
 
-            if let Err(why) = create_interaction_response.await {
-                eprintln!("Cannot respond to slash command: {}", why);
+                // Refresh the pinned dashboard in place, if one was created.
+                if let Some((channel, message_id)) = pinned {
+                    // The favicon attachment uploaded by `/pin-status` persists
+                    // across edits, so we only refresh the embed here.
+                    let embed = match get_server_status(&servers[0].addr, servers[0].port).await {
+                        Ok(status) => status.embed,
+                        Err(err) => CreateEmbed::default().description(err.to_string()).to_owned(),
+                    };
+                    let result = channel
+                        .edit_message(&ctx.http, message_id, |message| message.set_embed(embed))
+                        .await;
+                    if let Err(why) = result {
+                        error!(?why, "Failed to refresh pinned status message");
+                    }
+                }
             }
+        });
+    }
+}
+
+/// Post a plain notification embed to each subscribed channel.
+async fn announce(ctx: &Context, channels: &[ChannelId], text: &str) {
+    for channel in channels {
+        let result = channel
+            .send_message(&ctx.http, |message| {
+                message.set_embed(CreateEmbed::default().description(text).to_owned())
+            })
+            .await;
+        if let Err(why) = result {
+            error!(?why, "Failed to post status update to channel");
         }
     }
 }
 
+/// Build a string select menu listing `servers` so the user can pick which one
+/// to query.
+fn add_server_menu<'a>(
+    components: &'a mut serenity::builder::CreateComponents,
+    servers: &[McServer],
+) -> &'a mut serenity::builder::CreateComponents {
+    components.create_action_row(|row| {
+        row.create_select_menu(|menu| {
+            menu.custom_id("status_select")
+                .placeholder("Select a server")
+                .options(|options| {
+                    for server in servers {
+                        options.create_option(|option| {
+                            option.label(&server.name).value(&server.name)
+                        });
+                    }
+                    options
+                })
+        })
+    })
+}
+
+/// Attach the `status_refresh` button beneath the status embed so users can
+/// re-poll the server without re-typing the command.
+fn add_refresh_button<'a>(
+    components: &'a mut serenity::builder::CreateComponents,
+    server: &str,
+) -> &'a mut serenity::builder::CreateComponents {
+    // Encode the server in the custom id so the handler re-polls the same one
+    // instead of falling back to the first configured server.
+    let custom_id = format!("status_refresh:{server}");
+    components.create_action_row(|row| {
+        row.create_button(|button| {
+            button
+                .custom_id(custom_id)
+                .label("Refresh")
+                .style(ButtonStyle::Secondary)
+        })
+    })
+}
+
 #[shuttle_service::main]
 async fn serenity(
     #[shuttle_secrets::Secrets] secret_store: SecretStore,
@@ -73,19 +471,47 @@ async fn serenity(
         .get("DISCORD_TOKEN")
         .context("'DISCORD_TOKEN' was not found")?;
 
-    let mc_server_addr = secret_store
-        .get("MC_SERVER_ADDR")
-        .context("'MC_SERVER_ADDR' was not found")?;
+    // `MC_SERVERS` lists named servers as `name|addr|port` entries separated by
+    // `;`. If it is absent we fall back to the single `MC_SERVER_ADDR`/`PORT`
+    // pair so existing deployments keep working unchanged.
+    let servers = match secret_store.get("MC_SERVERS") {
+        Some(raw) => parse_servers(&raw).context("'MC_SERVERS' could not be parsed")?,
+        None => {
+            let addr = secret_store
+                .get("MC_SERVER_ADDR")
+                .context("'MC_SERVER_ADDR' was not found")?;
+            let port = secret_store
+                .get("MC_SERVER_PORT")
+                .and_then(|s| s.parse().ok())
+                .context("'MC_SERVER_PORT' was not found")?;
+            vec![McServer {
+                name: addr.clone(),
+                addr,
+                port,
+            }]
+        }
+    };
 
-    let mc_server_port = secret_store
-        .get("MC_SERVER_PORT")
-        .and_then(|s| s.parse().ok())
-        .context("'MC_SERVER_PORT' was not found")?;
+    let discord_guild_id = GuildId(
+        secret_store
+            .get("DISCORD_GUILD_ID")
+            .and_then(|s| s.parse().ok())
+            .context("'DISCORD_GUILD_ID' was not found")?,
+    );
 
-    let discord_guild_id = secret_store
-        .get("DISCORD_GUILD_ID")
-        .and_then(|s| s.parse().ok())
-        .context("'DISCORD_GUILD_ID' was not found")?;
+    // Open the embedded store and load any persisted settings. On first run the
+    // server list from the secrets above seeds the config; afterwards the stored
+    // list is used as is and the list parsed above is ignored.
+    let store = Store::open("mc-status-bot.sled").context("failed to open storage")?;
+    let mut settings = store
+        .load(discord_guild_id)
+        .context("failed to load guild settings")?;
+    if settings.servers.is_empty() {
+        settings.servers = servers;
+        store
+            .save(discord_guild_id, &settings)
+            .context("failed to seed guild settings")?;
+    }
 
     // Set gateway intents, which decides what events the bot will be notified about.
     // Here we don't need any intents so empty
@@ -93,8 +519,9 @@ async fn serenity(
 
     let client = Client::builder(discord_token, intents)
         .event_handler(Bot {
-            mc_server: (mc_server_addr, mc_server_port),
-            discord_guild_id: GuildId(discord_guild_id),
+            discord_guild_id,
+            store,
+            settings: Arc::new(Mutex::new(settings)),
         })
         .await
         .expect("Err creating client");
@@ -102,10 +529,69 @@ async fn serenity(
     Ok(client)
 }
 
+/// Parse the `MC_SERVERS` secret into a list of named servers. Each entry is
+/// `name|addr|port`, entries separated by `;`.
+fn parse_servers(raw: &str) -> anyhow::Result<Vec<McServer>> {
+    let servers: Vec<McServer> = raw
+        .split(';')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let mut parts = entry.split('|').map(str::trim);
+            let name = parts.next().context("missing server name")?;
+            let addr = parts.next().context("missing server address")?;
+            let port = parts
+                .next()
+                .context("missing server port")?
+                .parse()
+                .context("invalid server port")?;
+            Ok(McServer {
+                name: name.to_owned(),
+                addr: addr.to_owned(),
+                port,
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    // An empty list would let `servers[0]` panic at runtime, so reject it here.
+    anyhow::ensure!(!servers.is_empty(), "no servers configured");
+    Ok(servers)
+}
+
+/// Poll the server for its current `(online, max)` player counts, used by the
+/// background monitor which only cares about reachability and population.
+async fn poll_server(addr: &str, port: u16) -> Result<(u32, u32), async_minecraft_ping::ServerError> {
+    let config = ConnectionConfig::build(addr).with_port(port);
+    let connection = config.connect().await?;
+    let connection = connection.status().await?;
+    Ok((
+        connection.status.players.online,
+        connection.status.players.max,
+    ))
+}
+
+/// File name used when attaching the decoded favicon so the embed can reference
+/// it via `attachment://`.
+const FAVICON_FILE: &str = "favicon.png";
+
+/// The status embed plus the optional decoded favicon to attach alongside it.
+struct ServerStatus {
+    embed: CreateEmbed,
+    favicon: Option<Vec<u8>>,
+}
+
+impl From<CreateEmbed> for ServerStatus {
+    fn from(embed: CreateEmbed) -> Self {
+        ServerStatus {
+            embed,
+            favicon: None,
+        }
+    }
+}
+
 async fn get_server_status(
     addr: &str,
     port: u16,
-) -> Result<CreateEmbed, async_minecraft_ping::ServerError> {
+) -> Result<ServerStatus, async_minecraft_ping::ServerError> {
     let config = ConnectionConfig::build(addr).with_port(port);
 
     let connection = config.connect().await?;
@@ -122,34 +608,33 @@ async fn get_server_status(
         "".to_owned()
     };
 
-    let desc = match connection.status.description {
-        async_minecraft_ping::ServerDescription::Plain(ref desc) => desc,
-        async_minecraft_ping::ServerDescription::Object { ref text } => text,
-    }
-    .to_owned();
+    // `connection.status.description` is the crate's already-reduced type and has
+    // dropped any `extra` children, so re-fetch the raw status JSON and walk its
+    // full chat-component tree; fall back to the typed description if the extra
+    // ping fails. Either way we drop the legacy `§` colour codes afterwards.
+    let desc = match fetch_status_json(addr, port).await {
+        Ok(json) => strip_color_codes(&flatten_chat_component(&json["description"])),
+        Err(_) => match serde_json::to_value(&connection.status.description) {
+            Ok(value) => strip_color_codes(&flatten_chat_component(&value)),
+            Err(_) => String::new(),
+        },
+    };
 
     let playercount = (
         connection.status.players.online,
         connection.status.players.max,
     );
 
+    let favicon = connection
+        .status
+        .favicon
+        .as_deref()
+        .and_then(decode_favicon);
+
     let start = tokio::time::Instant::now();
     connection.ping(299792458).await?;
     let latency = start.elapsed();
 
-    // let message = serde_json::json!({
-    //     "embeds": [
-    //         {
-    //             "type": "rich",
-    //             "title": desc,
-    //             "description": format!("Players ({}/{}):\n{}", playercount.0, playercount.1, players),
-    //             "footer": {
-    //                 "text": format!("Ping: {} ms", latency.as_millis())
-    //             }
-    //         }
-    //     ]
-    // });
-
     let mut embed = CreateEmbed::default();
 
     embed
@@ -164,5 +649,131 @@ async fn get_server_status(
                 .to_owned(),
         );
 
-    Ok(embed)
+    if favicon.is_some() {
+        embed.thumbnail(format!("attachment://{FAVICON_FILE}"));
+    }
+
+    Ok(ServerStatus { embed, favicon })
+}
+
+/// Fetch the raw Server List Ping status JSON directly over TCP, bypassing the
+/// crate's reduced `ServerDescription` type so the full description component
+/// tree (including `extra` children) is available to [`flatten_chat_component`].
+async fn fetch_status_json(addr: &str, port: u16) -> anyhow::Result<serde_json::Value> {
+    let mut stream = TcpStream::connect((addr, port)).await?;
+
+    // Handshake: protocol version (-1 = unknown), server address, port, and the
+    // "next state" of 1 for status.
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, -1);
+    write_varint(&mut handshake, addr.len() as i32);
+    handshake.extend_from_slice(addr.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+    stream.write_all(&framed_packet(0x00, &handshake)).await?;
+
+    // Empty status-request packet.
+    stream.write_all(&framed_packet(0x00, &[])).await?;
+    stream.flush().await?;
+
+    // Response: packet length, packet id, then the length-prefixed JSON string.
+    let _packet_len = read_varint(&mut stream).await?;
+    let _packet_id = read_varint(&mut stream).await?;
+    let json_len = read_varint(&mut stream).await?;
+    anyhow::ensure!(json_len >= 0, "negative status length");
+    let mut buf = vec![0u8; json_len as usize];
+    stream.read_exact(&mut buf).await?;
+    Ok(serde_json::from_slice(&buf)?)
+}
+
+/// Wrap `body` behind its packet id as a length-prefixed Minecraft packet.
+fn framed_packet(id: i32, body: &[u8]) -> Vec<u8> {
+    let mut data = Vec::new();
+    write_varint(&mut data, id);
+    data.extend_from_slice(body);
+    let mut packet = Vec::new();
+    write_varint(&mut packet, data.len() as i32);
+    packet.extend_from_slice(&data);
+    packet
+}
+
+/// Append `value` to `buf` in Minecraft's little-endian base-128 VarInt form.
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut remaining = value as u32;
+    loop {
+        let mut byte = (remaining & 0x7F) as u8;
+        remaining >>= 7;
+        if remaining != 0 {
+            byte |= 0x80;
+        }
+        buf.push(byte);
+        if remaining == 0 {
+            break;
+        }
+    }
+}
+
+/// Read a Minecraft VarInt from `reader`.
+async fn read_varint<R: AsyncReadExt + Unpin>(reader: &mut R) -> std::io::Result<i32> {
+    let mut result: i32 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = reader.read_u8().await?;
+        result |= ((byte & 0x7F) as i32) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift >= 35 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "VarInt is too long",
+            ));
+        }
+    }
+    Ok(result)
+}
+
+/// Concatenate the `text` fields of a Minecraft chat component and all of its
+/// nested `extra` children into a single string.
+fn flatten_chat_component(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(parts) => parts.iter().map(flatten_chat_component).collect(),
+        serde_json::Value::Object(map) => {
+            let mut text = map
+                .get("text")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_owned();
+            if let Some(extra) = map.get("extra") {
+                text.push_str(&flatten_chat_component(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// Remove legacy `§x` colour and formatting codes from MOTD text.
+fn strip_color_codes(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '§' {
+            chars.next();
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Decode the base64 `data:image/png;base64,...` favicon field into raw PNG
+/// bytes, returning `None` if it is missing or malformed.
+fn decode_favicon(favicon: &str) -> Option<Vec<u8>> {
+    let encoded = favicon.strip_prefix("data:image/png;base64,")?;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .ok()
 }